@@ -0,0 +1,41 @@
+use ethers::{
+    providers::{Middleware, Provider, Ws},
+    types::{transaction::eip2718::TypedTransaction, transaction::eip2930::AccessList, Eip1559TransactionRequest},
+};
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use log::warn;
+
+/// Ask the node to compute the access list for `tx` via `eth_createAccessList`.
+/// Warm-access hints for the pair contracts, token contracts, and router
+/// lower cold SLOAD/account-access gas costs. Because the node has to
+/// execute the call once to build the list, a populated `error` field means
+/// it predicts `startArbitrage` would revert, which we surface as `Err` so
+/// the caller aborts the broadcast instead of paying gas for a transaction
+/// we already know will fail. A failure of the RPC call itself (e.g. the
+/// node doesn't support `eth_createAccessList`) is a weaker signal and is
+/// reported as `Ok(None)` so the caller can fall back to broadcasting
+/// without an access list.
+pub async fn build_access_list(
+    provider: Arc<Provider<Ws>>,
+    tx: &Eip1559TransactionRequest,
+) -> Result<Option<AccessList>> {
+    let typed_tx = TypedTransaction::Eip1559(tx.clone());
+
+    let result = match provider.create_access_list(&typed_tx, None).await {
+        Ok(result) => result,
+        Err(e) => {
+            warn!("eth_createAccessList failed, broadcasting without an access list: {:?}", e);
+            return Ok(None);
+        }
+    };
+
+    if let Some(error) = &result.error {
+        return Err(anyhow!(
+            "eth_createAccessList predicts the transaction would revert: {}",
+            error
+        ));
+    }
+
+    Ok(Some(result.access_list))
+}
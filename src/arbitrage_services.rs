@@ -1,9 +1,9 @@
 use ethers::{
-    abi::Abi,
+    abi::parse_abi,
     contract:: Contract,
     providers::{Middleware, Provider, Ws},
     signers::{LocalWallet, Signer},
-    types::{transaction::eip2718::TypedTransaction, BlockNumber, Eip1559TransactionRequest, H160, U256, U64},
+    types::{transaction::eip2718::TypedTransaction, BlockNumber, Eip1559TransactionRequest, Transaction, H160, U256, U64},
 };
 use std::sync::Arc;
 use chrono::Utc;
@@ -11,7 +11,11 @@ use anyhow::{Result, anyhow};
 use futures_util::stream::StreamExt;
 use log::{info, error};
 
+use crate::access_list::build_access_list;
+use crate::bindings::{ArbitrageContract, UniswapV2Pair};
 use crate::constants::{SUSHISWAP_FACTORY_ADDRESS, UNISWAP_V2_FACTORY_ADDRESS};
+use crate::fees::estimate_fees;
+use crate::simulation::dry_run_arbitrage;
 use crate::utils::*;
 
 pub enum TradeDirections {
@@ -20,12 +24,6 @@ pub enum TradeDirections {
 }
 
 
-pub fn load_contract_abi() -> Result<Abi> {
-    Ok(CONTRACT_ABI.clone())
-}
-
-
-
 pub async fn simulate_transaction(
     provider: Arc<Provider<Ws>>,
     use_sushiswap: bool,
@@ -39,8 +37,8 @@ pub async fn simulate_transaction(
     };
 
     let pool_address = get_pool_address(provider.clone(), factory_address, token_in, token_out).await?;
-    let pool = Contract::new(pool_address, POOL_ABI.clone(), provider.clone());
-    let reserves: (U256, U256, U256) = pool.method("getReserves", ())?.call().await?;
+    let pool = UniswapV2Pair::new(pool_address, provider.clone());
+    let reserves: (U256, U256, u32) = pool.get_reserves().call().await?;
 
     let (reserve_in, reserve_out) = if token_in < token_out {
         (reserves.0, reserves.1)
@@ -97,33 +95,26 @@ pub fn simulate_trade_profit(
 
 async fn execute_arbitrage(
     provider: Arc<Provider<Ws>>,
-    contract: Arc<Contract<Provider<Ws>>>,
+    contract: Arc<ArbitrageContract<Provider<Ws>>>,
     wallet: Arc<LocalWallet>,
     token_in: H160,
     token_out: H160,
     amount_in: U256,
     direction: TradeDirections,
 ) -> Result<()> {
-    let method_name = "startArbitrage";
-    //let gas_price = provider.get_gas_price().await?;
     let deadline = U256::from(Utc::now().timestamp() + 300);
 
-    let block = provider
-        .get_block(BlockNumber::Latest)
-        .await?
-        .ok_or(anyhow!("Failed to fetch latest block"))?;
-    let base_fee = block.base_fee_per_gas.ok_or(anyhow!("Base fee not available"))?;
-
-    let max_priority_per_gas = base_fee
-        .checked_div(U256::from(10))
-        .unwrap_or(U256::from(2_000_000_000));
+    let fee_estimate = estimate_fees(provider.clone()).await?;
+    let max_priority_per_gas = fee_estimate.max_priority_fee_per_gas;
+    let max_fee_per_gas = fee_estimate.max_fee_per_gas;
 
-    let max_fee_per_gas = base_fee + max_priority_per_gas;
-
-    let method = contract.method::<_, ()>(
-        method_name,
-        (token_in, amount_in, token_out, deadline, matches!(direction, TradeDirections::UNISWAP)),
-    )?;
+    let method = contract.start_arbitrage(
+        token_in,
+        amount_in,
+        token_out,
+        deadline,
+        matches!(direction, TradeDirections::UNISWAP),
+    );
 
     let calldata = method.calldata().ok_or(anyhow!("Calldata not available"))?;
 
@@ -135,6 +126,7 @@ async fn execute_arbitrage(
     };
 
     let tx = Eip1559TransactionRequest {
+        from: Some(wallet.address()),
         to: Some(contract.address().into()),
         data: Some(calldata),
         gas: None,
@@ -145,7 +137,18 @@ async fn execute_arbitrage(
         chain_id: Some(chain_id_u64),
         ..Default::default()
     };
-    
+
+    // Warm the pair/token/router slots `startArbitrage` touches so the
+    // broadcast tx pays less for cold SLOADs, and treat a clean response as
+    // one more pre-broadcast signal that the trade won't revert.
+    let tx = match build_access_list(provider.clone(), &tx).await? {
+        Some(access_list) => Eip1559TransactionRequest {
+            access_list,
+            ..tx
+        },
+        None => tx,
+    };
+
     let typed_tx = TypedTransaction::Eip1559(tx.clone());
     let gas_estimate = provider.estimate_gas(&typed_tx, None).await?;
 
@@ -183,7 +186,7 @@ async fn execute_arbitrage(
 
 pub async fn monitor_mempool(
     provider: Arc<Provider<Ws>>,
-    contract: Arc<Contract<Provider<Ws>>>,
+    contract: Arc<ArbitrageContract<Provider<Ws>>>,
     wallet: Arc<LocalWallet>,
     target_token_in: H160,
     target_token_out: H160,
@@ -204,8 +207,8 @@ pub async fn monitor_mempool(
         tokio::spawn(async move {
             if let Ok(Some(tx)) = provider.get_transaction(tx_hash).await {
                 if is_target_pair(&tx, target_token_in, target_token_out).await {
-                    if let Ok((token_in, token_out, amount_in)) = decode_transaction(&tx).await {
-                        if let Ok(Some((use_sushiswap, profit))) = check_price_discrepancy(provider.clone(), token_in, token_out, amount_in).await {
+                    if let Ok((token_in, token_out, amount_in)) = decode_transaction(&tx, target_token_in, target_token_out).await {
+                        if let Ok(Some((use_sushiswap, profit))) = check_price_discrepancy(provider.clone(), contract.clone(), &tx, token_in, token_out, amount_in).await {
                             let direction = if use_sushiswap {
                                 TradeDirections::SUSHISWAP
                             } else {
@@ -229,42 +232,114 @@ pub async fn monitor_mempool(
 }
 
 
-pub async fn check_price_discrepancy(
+/// Cheap closed-form pre-filter: estimate which DEX currently quotes the
+/// better output for `amount_in` using only `getReserves` and `x*y=k`. This
+/// is deliberately approximate (it ignores the victim's own price impact,
+/// transfer-fee tokens, and any revert in `startArbitrage`) and exists only
+/// to decide *which direction* is worth dry-running, not whether to trade.
+async fn estimate_direction(
     provider: Arc<Provider<Ws>>,
     token_in: H160,
     token_out: H160,
     amount_in: U256,
-) -> Result<Option<(bool, U256)>> {
+) -> Result<Option<bool>> {
     // Fees: 0.3% for Uniswap (997/1000), 0.25% for Sushiswap (998/1000)
     let fee_uniswap = U256::from(997);
     let fee_sushiswap = U256::from(998);
 
-    // Simulate reserves on Uniswap and Sushiswap
     let (uni_reserve_in, uni_reserve_out) =
         simulate_transaction(provider.clone(), false, token_in, token_out).await?;
     let (sushi_reserve_in, sushi_reserve_out) =
         simulate_transaction(provider.clone(), true, token_in, token_out).await?;
 
-    // Simulate trade output on Uniswap and Sushiswap
     let uni_output = simulate_trade_profit(uni_reserve_in, uni_reserve_out, amount_in, fee_uniswap)?;
     let sushi_output = simulate_trade_profit(sushi_reserve_in, sushi_reserve_out, amount_in, fee_sushiswap)?;
 
-    // Determine which DEX is cheaper to buy from and which is more expensive to sell on
-    let (buy_on_sushiswap, profit) = if sushi_output > uni_output {
-        // Sushiswap is cheaper to buy from, Uniswap is more expensive to sell on
-        (true, sushi_output - uni_output)
+    if sushi_output > uni_output {
+        Ok(Some(true))
     } else if uni_output > sushi_output {
-        // Uniswap is cheaper to buy from, Sushiswap is more expensive to sell on
-        (false, uni_output - sushi_output)
+        Ok(Some(false))
     } else {
-        // No price discrepancy
-        return Ok(None);
+        Ok(None)
+    }
+}
+
+/// Build the `balanceOf(address)` calldata for `token` without pulling in a
+/// full ERC-20 ABI — we only ever need this one read during simulation.
+fn balance_of_calldata(token: H160, provider: Arc<Provider<Ws>>, owner: H160) -> Result<ethers::types::Bytes> {
+    let erc20 = parse_abi(&["function balanceOf(address) view returns (uint256)"])?;
+    let token_contract = Contract::new(token, erc20, provider);
+    token_contract
+        .method::<_, U256>("balanceOf", owner)?
+        .calldata()
+        .ok_or_else(|| anyhow!("Failed to encode balanceOf calldata"))
+}
+
+/// Decide whether the pending `tx` is worth front/back-running, and if so in
+/// which direction. Unlike the old closed-form check, the actual go/no-go
+/// decision is made by `dry_run_arbitrage`: we replay `tx` and our own
+/// `startArbitrage` call against a forked-in-process EVM pinned at the
+/// latest block, and only report a discrepancy when that simulated
+/// execution succeeds end-to-end and leaves our contract strictly richer in
+/// `token_out`.
+pub async fn check_price_discrepancy(
+    provider: Arc<Provider<Ws>>,
+    contract: Arc<ArbitrageContract<Provider<Ws>>>,
+    tx: &Transaction,
+    token_in: H160,
+    token_out: H160,
+    amount_in: U256,
+) -> Result<Option<(bool, U256)>> {
+    let use_sushiswap = match estimate_direction(provider.clone(), token_in, token_out, amount_in).await? {
+        Some(direction) => direction,
+        None => return Ok(None),
+    };
+
+    let deadline = U256::from(Utc::now().timestamp() + 300);
+    let calldata = contract
+        .start_arbitrage(token_in, amount_in, token_out, deadline, !use_sushiswap)
+        .calldata()
+        .ok_or_else(|| anyhow!("Failed to encode startArbitrage calldata"))?;
+
+    let block_number = provider
+        .get_block(BlockNumber::Latest)
+        .await?
+        .and_then(|b| b.number)
+        .ok_or_else(|| anyhow!("Failed to fetch latest block number"))?
+        .as_u64();
+
+    let balance_calldata = balance_of_calldata(token_out, provider.clone(), contract.address())?;
+
+    let outcome = dry_run_arbitrage(
+        provider.clone(),
+        block_number,
+        tx,
+        contract.address(),
+        calldata,
+        balance_calldata,
+        token_out,
+    )
+    .await?;
+
+    let outcome = match outcome {
+        Some(outcome) => outcome,
+        None => return Ok(None),
+    };
+
+    // Net the simulated gas cost out of the gross token_out delta before
+    // comparing against the threshold, so we never broadcast a trade that's
+    // only profitable before paying for its own inclusion.
+    let fee_estimate = estimate_fees(provider).await?;
+    let gas_cost = U256::from(outcome.gas_used) * fee_estimate.effective_gas_price();
+    let net_profit = match outcome.profit.checked_sub(gas_cost) {
+        Some(net_profit) => net_profit,
+        None => return Ok(None),
     };
 
-    // Check if the profit exceeds the threshold (1e15 wei = 0.001 ETH)
-    let profit_threshold  = U256::exp10(15);
-    if profit > profit_threshold {
-        Ok(Some((buy_on_sushiswap, profit)))
+    // 1e15 wei = 0.001 ETH minimum net profit.
+    let profit_threshold = U256::exp10(15);
+    if net_profit > profit_threshold {
+        Ok(Some((use_sushiswap, net_profit)))
     } else {
         Ok(None)
     }
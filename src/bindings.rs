@@ -0,0 +1,31 @@
+use ethers::contract::abigen;
+
+// Type-safe contract bindings generated at compile time from the on-disk
+// ABIs, replacing the stringly-typed `contract.method("name", args)` calls
+// that used to live behind the `lazy_static! Abi` values in `utils.rs`.
+// Typos in a method name or a mismatched argument tuple are now caught by
+// the compiler instead of surfacing as a runtime `AbiError`.
+
+// `abigen!`'s path argument is resolved relative to `CARGO_MANIFEST_DIR`
+// (the crate root), unlike `include_str!` which resolves relative to this
+// source file, so these are bare filenames rather than `../...`.
+
+abigen!(
+    UniswapV2Factory,
+    "UniswapV2FactoryABI.json"
+);
+
+abigen!(
+    UniswapV2Pair,
+    "UniswapV2PairABI.json"
+);
+
+abigen!(
+    UniswapV2Router,
+    "UniswapV2RouterABI.json"
+);
+
+abigen!(
+    ArbitrageContract,
+    "ContractABI.json"
+);
@@ -0,0 +1,130 @@
+use ethers::{
+    providers::{Middleware, Provider, Ws},
+    types::{BlockNumber, U256},
+};
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+
+/// EIP-1559 target gas-used ratio: the protocol nudges `base_fee` up when a
+/// block is fuller than this and down when it's emptier.
+const TARGET_GAS_USED_RATIO: f64 = 0.5;
+
+/// Maximum per-block `base_fee` change is `base_fee / BASE_FEE_MAX_CHANGE_DENOMINATOR`
+/// (the familiar ±12.5%).
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Number of past blocks to sample when calling `eth_feeHistory`.
+pub const FEE_HISTORY_BLOCK_COUNT: u64 = 10;
+
+/// Reward percentile used to derive `max_priority_fee_per_gas` from recent
+/// blocks (10th/50th/90th are the conventional choices; we default to the
+/// median so we're competitive without over-tipping).
+pub const FEE_HISTORY_REWARD_PERCENTILE: f64 = 50.0;
+
+pub struct FeeEstimate {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+impl FeeEstimate {
+    /// The gas price we'd actually pay per unit of gas under EIP-1559:
+    /// `base_fee + priority_fee`, capped by `max_fee_per_gas`.
+    pub fn effective_gas_price(&self) -> U256 {
+        self.max_fee_per_gas
+    }
+}
+
+/// Derive a `max_priority_fee_per_gas` / `max_fee_per_gas` pair from
+/// `eth_feeHistory` instead of the crude `base_fee / 10` guess: the priority
+/// fee is the average of the requested reward percentile over the last
+/// `FEE_HISTORY_BLOCK_COUNT` blocks, and the fee cap is that priority fee on
+/// top of the base fee predicted for the next block via the standard
+/// EIP-1559 ±12.5% adjustment toward `TARGET_GAS_USED_RATIO`.
+pub async fn estimate_fees(provider: Arc<Provider<Ws>>) -> Result<FeeEstimate> {
+    let fee_history = provider
+        .fee_history(
+            FEE_HISTORY_BLOCK_COUNT,
+            BlockNumber::Latest,
+            &[FEE_HISTORY_REWARD_PERCENTILE],
+        )
+        .await
+        .map_err(|e| anyhow!("Failed to fetch eth_feeHistory: {:?}", e))?;
+
+    let rewards: Vec<U256> = fee_history
+        .reward
+        .iter()
+        .filter_map(|percentiles| percentiles.first().copied())
+        .collect();
+
+    if rewards.is_empty() {
+        return Err(anyhow!("eth_feeHistory returned no reward samples"));
+    }
+
+    let reward_sum = rewards
+        .iter()
+        .fold(U256::zero(), |acc, reward| acc + reward);
+    let max_priority_fee_per_gas = reward_sum / U256::from(rewards.len());
+
+    let latest_base_fee = *fee_history
+        .base_fee_per_gas
+        .last()
+        .ok_or_else(|| anyhow!("eth_feeHistory returned no base fee samples"))?;
+    let latest_gas_used_ratio = fee_history.gas_used_ratio.last().copied().unwrap_or(TARGET_GAS_USED_RATIO);
+
+    let predicted_base_fee = predict_next_base_fee(latest_base_fee, latest_gas_used_ratio);
+    let max_fee_per_gas = predicted_base_fee + max_priority_fee_per_gas;
+
+    Ok(FeeEstimate {
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+/// Predict the next block's `base_fee` from the latest known base fee and
+/// gas-used ratio, per EIP-1559: move toward `TARGET_GAS_USED_RATIO` by at
+/// most `1 / BASE_FEE_MAX_CHANGE_DENOMINATOR` of the current base fee.
+fn predict_next_base_fee(base_fee: U256, gas_used_ratio: f64) -> U256 {
+    if (gas_used_ratio - TARGET_GAS_USED_RATIO).abs() < f64::EPSILON {
+        return base_fee;
+    }
+
+    let max_change = base_fee / U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+    let ratio_delta = ((gas_used_ratio - TARGET_GAS_USED_RATIO) / TARGET_GAS_USED_RATIO).abs();
+    let scaled_change = max_change * U256::from((ratio_delta * 1_000.0) as u64) / U256::from(1_000);
+
+    if gas_used_ratio > TARGET_GAS_USED_RATIO {
+        base_fee + scaled_change
+    } else {
+        base_fee.saturating_sub(scaled_change)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_fee_is_unchanged_at_the_target_ratio() {
+        let base_fee = U256::from(100_000_000_000u64);
+        assert_eq!(predict_next_base_fee(base_fee, TARGET_GAS_USED_RATIO), base_fee);
+    }
+
+    #[test]
+    fn base_fee_rises_when_blocks_are_fuller_than_target() {
+        let base_fee = U256::from(100_000_000_000u64);
+        assert!(predict_next_base_fee(base_fee, 1.0) > base_fee);
+    }
+
+    #[test]
+    fn base_fee_falls_when_blocks_are_emptier_than_target() {
+        let base_fee = U256::from(100_000_000_000u64);
+        assert!(predict_next_base_fee(base_fee, 0.0) < base_fee);
+    }
+
+    #[test]
+    fn base_fee_change_is_capped_at_eip1559_max_change() {
+        let base_fee = U256::from(100_000_000_000u64);
+        let max_increase = base_fee / U256::from(BASE_FEE_MAX_CHANGE_DENOMINATOR);
+        assert_eq!(predict_next_base_fee(base_fee, 1.0), base_fee + max_increase);
+    }
+}
@@ -1,12 +1,16 @@
  
 
+mod access_list;
 mod arbitrage_services;
+mod bindings;
 mod constants;
+mod fees;
+mod router;
+mod simulation;
 mod utils;
 
 use ethers::{
     types::H160,
-    contract::Contract,
     providers::{Ws, Provider},
     signers::LocalWallet,
 
@@ -16,8 +20,9 @@ use std::sync::Arc;
 use std::env;
 use dotenv::dotenv;
 
+use bindings::ArbitrageContract;
 use constants::{CONTRACT_ADDRESS, DAI_ADDRESS, QUICKNODE_WS_URL, WETH_ADDRESS};
-use arbitrage_services::{load_contract_abi, monitor_mempool};
+use arbitrage_services::monitor_mempool;
 
 
 #[tokio::main]
@@ -30,16 +35,13 @@ async fn main() {
         .expect("Failed to connect to WebSocket provider");
     let provider = Arc::new(provider);
 
-    let contract_abi = load_contract_abi().expect("Failed to load contract ABI");
     let contract_address = CONTRACT_ADDRESS.parse::<H160>()
         .expect("Invalid contract address");
 
-    let contract = Arc::new(Contract::new(
-        contract_address, 
-        contract_abi, 
+    let contract = Arc::new(ArbitrageContract::new(
+        contract_address,
         provider.clone()
-    )
-);
+    ));
 
     let private_key = env::var("PRIVATE_KEY").expect("missing private key");
     let wallet = Arc::new(
@@ -0,0 +1,190 @@
+use ethers::{abi::AbiDecode, types::{Transaction, H160, U256}};
+use anyhow::{anyhow, Result};
+
+use crate::bindings::UniswapV2RouterCalls;
+
+/// A Uniswap V2 router swap call, normalized across every swap variant the
+/// router exposes so the rest of the bot only has to reason about one
+/// shape instead of assuming `swapExactTokensForTokens` with a two-token
+/// path.
+pub struct DecodedSwap {
+    /// The full route, in swap order (`path[0]` is sold, `path[last]` is bought).
+    pub path: Vec<H160>,
+    /// The amount named in the call: `amountIn` for exact-in variants
+    /// (the transaction's `value` for the ETH-in exact-in variants, which
+    /// have no `amountIn` field), `amountOut` for exact-out variants.
+    pub amount: U256,
+    /// `true` for `swap*Exact*For*` variants, `false` for `swap*For*Exact*`.
+    pub is_exact_in: bool,
+    /// `true` when the first leg of the path is native ETH (`swap*ETHFor*`).
+    pub in_is_eth: bool,
+    /// `true` when the last leg of the path is native ETH (`swap*ForETH*`).
+    pub out_is_eth: bool,
+}
+
+/// Decode `tx`'s calldata by matching its 4-byte selector against every
+/// swap function the Uniswap V2 router exposes, rather than assuming a
+/// single `swapExactTokensForTokens` shape.
+pub fn decode_router_swap(tx: &Transaction) -> Result<DecodedSwap> {
+    let call = UniswapV2RouterCalls::decode(&tx.input)
+        .map_err(|e| anyhow!("Failed to decode router calldata: {:?}", e))?;
+
+    let decoded = match call {
+        UniswapV2RouterCalls::SwapExactTokensForTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_in,
+            is_exact_in: true,
+            in_is_eth: false,
+            out_is_eth: false,
+        },
+        UniswapV2RouterCalls::SwapTokensForExactTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_out,
+            is_exact_in: false,
+            in_is_eth: false,
+            out_is_eth: false,
+        },
+        // ETH-in variants carry no `amountIn` calldata field — the amount
+        // actually sold is the transaction's `value`, not `amountOutMin`
+        // (which is only the output slippage floor).
+        UniswapV2RouterCalls::SwapExactETHForTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: tx.value,
+            is_exact_in: true,
+            in_is_eth: true,
+            out_is_eth: false,
+        },
+        UniswapV2RouterCalls::SwapTokensForExactETH(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_out,
+            is_exact_in: false,
+            in_is_eth: false,
+            out_is_eth: true,
+        },
+        UniswapV2RouterCalls::SwapExactTokensForETH(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_in,
+            is_exact_in: true,
+            in_is_eth: false,
+            out_is_eth: true,
+        },
+        UniswapV2RouterCalls::SwapETHForExactTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_out,
+            is_exact_in: false,
+            in_is_eth: true,
+            out_is_eth: false,
+        },
+        UniswapV2RouterCalls::SwapExactTokensForTokensSupportingFeeOnTransferTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_in,
+            is_exact_in: true,
+            in_is_eth: false,
+            out_is_eth: false,
+        },
+        // Same as `SwapExactETHForTokens` above: the input amount is `tx.value`.
+        UniswapV2RouterCalls::SwapExactETHForTokensSupportingFeeOnTransferTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: tx.value,
+            is_exact_in: true,
+            in_is_eth: true,
+            out_is_eth: false,
+        },
+        UniswapV2RouterCalls::SwapExactTokensForETHSupportingFeeOnTransferTokens(c) => DecodedSwap {
+            path: c.path,
+            amount: c.amount_in,
+            is_exact_in: true,
+            in_is_eth: false,
+            out_is_eth: true,
+        },
+        _ => return Err(anyhow!("Calldata is not one of the router's swap functions")),
+    };
+
+    if decoded.path.len() < 2 {
+        return Err(anyhow!("Swap path must contain at least two tokens"));
+    }
+
+    Ok(decoded)
+}
+
+/// Index of the hop in `path` whose two tokens are `target_token_a` and
+/// `target_token_b` (in either order), if the path touches that pair at all.
+pub fn find_target_hop(path: &[H160], target_token_a: H160, target_token_b: H160) -> Option<usize> {
+    path.windows(2).position(|hop| {
+        (hop[0] == target_token_a && hop[1] == target_token_b)
+            || (hop[0] == target_token_b && hop[1] == target_token_a)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi::AbiEncode;
+    use ethers::types::Transaction;
+
+    use crate::bindings::{SwapExactTokensForTokensCall, SwapTokensForExactTokensCall};
+
+    fn tx_with_input(input: Vec<u8>) -> Transaction {
+        Transaction {
+            input: input.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_exact_tokens_for_tokens() {
+        let path = vec![H160::repeat_byte(1), H160::repeat_byte(2), H160::repeat_byte(3)];
+        let call = SwapExactTokensForTokensCall {
+            amount_in: U256::from(100),
+            amount_out_min: U256::zero(),
+            path: path.clone(),
+            to: H160::zero(),
+            deadline: U256::zero(),
+        };
+
+        let decoded = decode_router_swap(&tx_with_input(call.encode())).unwrap();
+
+        assert_eq!(decoded.path, path);
+        assert_eq!(decoded.amount, U256::from(100));
+        assert!(decoded.is_exact_in);
+        assert!(!decoded.in_is_eth);
+        assert!(!decoded.out_is_eth);
+    }
+
+    #[test]
+    fn decodes_tokens_for_exact_tokens_as_exact_out() {
+        let path = vec![H160::repeat_byte(1), H160::repeat_byte(2)];
+        let call = SwapTokensForExactTokensCall {
+            amount_out: U256::from(100),
+            amount_in_max: U256::from(200),
+            path: path.clone(),
+            to: H160::zero(),
+            deadline: U256::zero(),
+        };
+
+        let decoded = decode_router_swap(&tx_with_input(call.encode())).unwrap();
+
+        assert_eq!(decoded.path, path);
+        assert_eq!(decoded.amount, U256::from(100));
+        assert!(!decoded.is_exact_in);
+    }
+
+    #[test]
+    fn rejects_calldata_that_is_not_a_swap() {
+        // Four arbitrary bytes that don't match any router swap selector.
+        assert!(decode_router_swap(&tx_with_input(vec![0xde, 0xad, 0xbe, 0xef])).is_err());
+    }
+
+    #[test]
+    fn find_target_hop_matches_any_adjacent_pair_in_either_order() {
+        let a = H160::repeat_byte(1);
+        let b = H160::repeat_byte(2);
+        let c = H160::repeat_byte(3);
+        let path = vec![a, b, c];
+
+        assert_eq!(find_target_hop(&path, a, b), Some(0));
+        assert_eq!(find_target_hop(&path, b, c), Some(1));
+        assert_eq!(find_target_hop(&path, c, b), Some(1));
+        assert_eq!(find_target_hop(&path, a, c), None);
+    }
+}
@@ -0,0 +1,165 @@
+use ethers::{
+    providers::{Provider, Ws},
+    types::{Bytes, Transaction, H160, U256},
+};
+use revm::{
+    db::{CacheDB, EthersDB},
+    primitives::{Address as RAddress, ExecutionResult, TransactTo, U256 as RU256},
+    EVM,
+};
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use log::info;
+
+/// Result of dry-running our arbitrage against a post-victim EVM state.
+pub struct DryRunOutcome {
+    /// `startArbitrage`'s-eye view of how much `token_out` our contract gained.
+    pub profit: U256,
+    /// Gas the simulated `startArbitrage` call consumed, for net-profit accounting.
+    pub gas_used: u64,
+}
+
+/// Gas limit used for our own simulated calls (`balanceOf` reads and
+/// `startArbitrage` itself). Deliberately independent of the victim's own
+/// gas limit: `startArbitrage` runs two swaps plus bookkeeping and can
+/// legitimately need more gas than a simple victim swap, so inheriting
+/// `victim_tx.gas` risked a profitable arbitrage spuriously "reverting"
+/// out-of-gas in simulation.
+const SIMULATED_CALL_GAS_LIMIT: u64 = 3_000_000;
+
+fn to_address(addr: H160) -> RAddress {
+    RAddress::from(addr.0)
+}
+
+fn to_revm_u256(value: U256) -> RU256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    RU256::from_be_bytes(bytes)
+}
+
+/// Replay `victim_tx` and then our own `startArbitrage` call against a
+/// `CacheDB<EthersDB<Provider<Ws>>>` pinned at `block_number`, so that the
+/// second call sees exactly the reserves/state the victim's trade leaves
+/// behind. Returns `Ok(None)` if either leg reverts or if the arbitrage
+/// contract's `token_out` balance does not increase; otherwise returns that
+/// balance delta together with the gas `startArbitrage` used.
+pub async fn dry_run_arbitrage(
+    provider: Arc<Provider<Ws>>,
+    block_number: u64,
+    victim_tx: &Transaction,
+    arb_contract: H160,
+    arb_calldata: Bytes,
+    token_out_balance_of_calldata: Bytes,
+    token_out: H160,
+) -> Result<Option<DryRunOutcome>> {
+    let ethers_db = EthersDB::new(provider.clone(), Some(block_number.into()))
+        .ok_or_else(|| anyhow!("Failed to initialize EthersDB at block {}", block_number))?;
+    let mut cache_db = CacheDB::new(ethers_db);
+
+    let block = provider
+        .get_block(block_number)
+        .await?
+        .ok_or_else(|| anyhow!("Failed to fetch block {} for simulation", block_number))?;
+
+    let mut evm = EVM::new();
+    evm.env.block.number = to_revm_u256(U256::from(block_number));
+    evm.env.block.timestamp = to_revm_u256(block.timestamp);
+    evm.env.block.basefee = block
+        .base_fee_per_gas
+        .map(to_revm_u256)
+        .unwrap_or_default();
+    // We're replaying historical/pending calldata rather than constructing
+    // real EIP-1559 transactions here, so `tx.gas_price` is left at its
+    // zero default; without this, revm's post-London validation rejects
+    // every `transact_commit`/`transact_ref` call as `gas_price < basefee`
+    // before execution ever runs.
+    evm.env.cfg.disable_base_fee_check = true;
+    evm.database(cache_db.clone());
+
+    // Step 1: replay the victim's swap and commit the resulting state diff
+    // (new reserves, updated balances) into the CacheDB.
+    let victim_to = victim_tx
+        .to
+        .ok_or_else(|| anyhow!("Victim transaction has no `to` address"))?;
+
+    evm.env.tx.caller = to_address(victim_tx.from);
+    evm.env.tx.transact_to = TransactTo::Call(to_address(victim_to));
+    evm.env.tx.data = victim_tx.input.0.clone();
+    evm.env.tx.value = to_revm_u256(victim_tx.value);
+    evm.env.tx.gas_limit = victim_tx.gas.as_u64();
+
+    let victim_result = evm
+        .transact_commit()
+        .map_err(|e| anyhow!("Victim tx simulation failed: {:?}", e))?;
+
+    if !matches!(victim_result, ExecutionResult::Success { .. }) {
+        info!("Victim transaction reverts in simulation, skipping arbitrage");
+        return Ok(None);
+    }
+
+    // Step 2: read our contract's token_out balance against the post-victim
+    // state, then replay `startArbitrage` and read it again.
+    cache_db = evm.db.take().expect("database was set above");
+
+    evm.env.tx.caller = to_address(arb_contract);
+    evm.env.tx.transact_to = TransactTo::Call(to_address(token_out));
+    evm.env.tx.data = token_out_balance_of_calldata.0.clone();
+    evm.env.tx.value = RU256::ZERO;
+    evm.env.tx.gas_limit = SIMULATED_CALL_GAS_LIMIT;
+    evm.database(cache_db.clone());
+
+    let before = read_uint_output(
+        &evm.transact_ref()
+            .map_err(|e| anyhow!("balanceOf (before) failed: {:?}", e))?
+            .result,
+    )
+    .ok_or_else(|| anyhow!("Failed to read pre-trade token_out balance"))?;
+
+    cache_db = evm.db.take().expect("database was set above");
+    evm.env.tx.caller = to_address(arb_contract);
+    evm.env.tx.transact_to = TransactTo::Call(to_address(arb_contract));
+    evm.env.tx.data = arb_calldata.0.clone();
+    evm.env.tx.value = RU256::ZERO;
+    evm.env.tx.gas_limit = SIMULATED_CALL_GAS_LIMIT;
+    evm.database(cache_db.clone());
+
+    let arb_result = evm
+        .transact_commit()
+        .map_err(|e| anyhow!("Arbitrage tx simulation failed: {:?}", e))?;
+
+    let gas_used = match arb_result {
+        ExecutionResult::Success { gas_used, .. } => gas_used,
+        _ => {
+            info!("startArbitrage reverts in simulation, skipping broadcast");
+            return Ok(None);
+        }
+    };
+
+    cache_db = evm.db.take().expect("database was set above");
+    evm.env.tx.caller = to_address(arb_contract);
+    evm.env.tx.transact_to = TransactTo::Call(to_address(token_out));
+    evm.env.tx.data = token_out_balance_of_calldata.0.clone();
+    evm.env.tx.value = RU256::ZERO;
+    evm.env.tx.gas_limit = SIMULATED_CALL_GAS_LIMIT;
+    evm.database(cache_db);
+
+    let after = read_uint_output(&evm.transact_ref().map_err(|e| anyhow!("balanceOf (after) failed: {:?}", e))?.result)
+        .ok_or_else(|| anyhow!("Failed to read post-trade token_out balance"))?;
+
+    let profit = after.checked_sub(before).unwrap_or_default();
+    if profit.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(DryRunOutcome { profit, gas_used }))
+}
+
+fn read_uint_output(result: &ExecutionResult) -> Option<U256> {
+    match result {
+        ExecutionResult::Success {
+            output: revm::primitives::Output::Call(bytes),
+            ..
+        } => Some(U256::from_big_endian(bytes)),
+        _ => None,
+    }
+}
@@ -1,23 +1,16 @@
-use ethers::{ 
-    abi::Abi,
+use ethers::{
     types::{
         Transaction,
         transaction::eip2718::TypedTransaction,
-         H160, 
+         H160,
          U256
-        }, contract::Contract, providers::{Middleware, Provider, Ws}
+        }, providers::{Middleware, Provider, Ws}
 };
 use anyhow::{Result, anyhow};
 use std::sync::Arc;
-use lazy_static::lazy_static;
-
-lazy_static! {
-    pub static ref UNISWAP_V2_ROUTER_ABI: Abi = serde_json::from_str(include_str!("../UniswapV2RouterABI.json")).unwrap();
-    pub static ref POOL_ABI: Abi = serde_json::from_str(include_str!("../UniswapV2PairABI.json")).unwrap();
-    pub static ref FACTORY_ABI: Abi = serde_json::from_str(include_str!("../UniswapV2FactoryABI.json")).unwrap();
-    pub static ref CONTRACT_ABI: Abi = serde_json::from_str(include_str!("../ContractABI.json")).unwrap();
-}
 
+use crate::bindings::UniswapV2Factory;
+use crate::router::{decode_router_swap, find_target_hop};
 
 pub async fn get_pool_address(
     provider: Arc<Provider<Ws>>,
@@ -25,8 +18,8 @@ pub async fn get_pool_address(
     token_in: H160,
     token_out: H160,
 ) -> Result<H160> {
-    // Create a contract instance for the factory
-    let factory = Contract::new(factory_address, FACTORY_ABI.clone(), provider.clone());
+    // Create a typed binding for the factory
+    let factory = UniswapV2Factory::new(factory_address, provider.clone());
 
     // Ensure tokens are in canonical order (lower address first)
     let (token0, token1) = if token_in < token_out {
@@ -37,7 +30,7 @@ pub async fn get_pool_address(
 
     // Call the `getPair` method to get the pool address
     let pair_address: H160 = factory
-        .method("getPair", (token0, token1))?
+        .get_pair(token0, token1)
         .call()
         .await
         .map_err(|e| {
@@ -71,12 +64,12 @@ pub async fn get_gas_estimate(tx: &TypedTransaction, provider: Arc<Provider<Ws>>
         .map_err(|e| anyhow!("Failed to get gas estimate: {:?}", e))
 }
 
+/// Whether `tx` is a router swap touching the target pair anywhere along
+/// its path, not just as the first hop.
 pub async fn is_target_pair(tx: &Transaction, target_token_in: H160, target_token_out: H160) -> bool {
-    let decoded_tx = decode_transaction(tx).await;
-    match decoded_tx {
-        Ok((token_in, token_out, _)) => {
-            let is_match = (token_in == target_token_in && token_out == target_token_out)
-                || (token_in == target_token_out && token_out == target_token_in);
+    match decode_router_swap(tx) {
+        Ok(swap) => {
+            let is_match = find_target_hop(&swap.path, target_token_in, target_token_out).is_some();
             if !is_match {
                 log::info!("Transaction does not involve the target token pair: {:?}", tx.hash);
             }
@@ -88,38 +81,45 @@ pub async fn is_target_pair(tx: &Transaction, target_token_in: H160, target_toke
         }
     }
 }
-pub async fn decode_transaction(tx: &Transaction) -> Result<(H160, H160, U256)> {
-    let func = UNISWAP_V2_ROUTER_ABI
-        .function("swapExactTokensForTokens")
-        .map_err(|e| anyhow!("Failed to load UniswapV2Router function: {:?}", e))?;
-
-    let decoded = func
-        .decode_input(&tx.input)
-        .map_err(|e| anyhow!("Failed to decode transaction: {:?}", e))?;
-
-    // Extract amountIn (U256)
-    let amount_in = decoded[0]
-        .clone()
-        .into_uint()
-        .ok_or(anyhow!("Error decoding amount_in"))?;
-
-    // Extract path (Vec<H160>)
-    let path = decoded[2]
-        .clone()
-        .into_array()
-        .ok_or(anyhow!("Error decoding path"))?;
-
-    // Extract token_in and token_out from the path
-    let token_in = path[0]
-        .clone()
-        .into_address()
-        .ok_or(anyhow!("Error decoding token_in"))?;
-    let token_out = path[1]
-        .clone()
-        .into_address()
-        .ok_or(anyhow!("Error decoding token_out"))?;
-
-    Ok((token_in, token_out, amount_in))
+
+/// Decode `tx` and return the `(token_in, token_out, amount_in)` of the
+/// specific hop along its (possibly multi-hop) path that contains the
+/// target pair, rather than always assuming `path[0]`/`path[1]`.
+///
+/// `swap.amount` is the amount named in the *overall* call: `amountIn` for
+/// exact-in variants, but `amountOut` for exact-out variants (`swap*For
+/// Exact*`) — the latter names no input amount at all, only a maximum
+/// (`amountInMax`/`msg.value`) the caller is willing to spend, so it can't
+/// stand in for `amount_in` without mis-sizing our own trade. We also only
+/// equal the hop's real input when the matched hop is the first leg of the
+/// path — for any later hop, the real input depends on the unknown output
+/// of the earlier leg(s), which we don't derive (it would require another
+/// simulation pass). Both cases bail out rather than hand back a
+/// plausible-looking but wrong number.
+pub async fn decode_transaction(
+    tx: &Transaction,
+    target_token_in: H160,
+    target_token_out: H160,
+) -> Result<(H160, H160, U256)> {
+    let swap = decode_router_swap(tx)?;
+
+    if !swap.is_exact_in {
+        return Err(anyhow!(
+            "Exact-out swaps don't name an input amount; skipping"
+        ));
+    }
+
+    let hop = find_target_hop(&swap.path, target_token_in, target_token_out)
+        .ok_or_else(|| anyhow!("Target pair not found along swap path"))?;
+
+    if hop != 0 {
+        return Err(anyhow!(
+            "Target pair is at hop {} of a multi-hop path; the per-hop input amount is unknown without re-simulating the earlier legs",
+            hop
+        ));
+    }
+
+    Ok((swap.path[hop], swap.path[hop + 1], swap.amount))
 }
 
 